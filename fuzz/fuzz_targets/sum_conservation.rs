@@ -0,0 +1,60 @@
+#![no_main]
+
+//! Property fuzz target for the split invariants.
+//!
+//! The hand-written tests enumerate a fixed set of cases, but the core
+//! guarantees — the shares sum back to the input mantissa, there is exactly one
+//! share per recipient, and (for non-negative amounts) the shares come out in
+//! non-increasing order — are exactly the kind of property a fuzzer is good at
+//! breaking. This target feeds `split_decimal` a random mantissa, recipient
+//! count, and scale and asserts all three.
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use divvysim::split::split_decimal;
+
+/// A randomly generated split request.
+#[derive(Debug, Arbitrary)]
+struct SplitInput {
+    /// Raw mantissa, covering positive, negative, and zero amounts.
+    mantissa: i64,
+    /// Recipient seed; mapped below so we still exercise 0, 1, and large counts
+    /// without asking the fuzzer to allocate an unbounded vector.
+    recipients_seed: u32,
+    /// Desired output scale, constrained to the Decimal-legal `0..=28`.
+    scale_seed: u8,
+}
+
+fuzz_target!(|input: SplitInput| {
+    let amount = Decimal::from_i128_with_scale(input.mantissa as i128, 0);
+    // Bound the recipient count so a "huge" value stays testable, while still
+    // reaching 0 and 1 regularly.
+    let recipients = (input.recipients_seed % 200_001) as usize;
+    let scale = (input.scale_seed % 29) as u32;
+
+    // Zero recipients is an undefined split; the fallible API reports it and the
+    // infallible one panics, so there is nothing to check here.
+    if recipients == 0 {
+        return;
+    }
+
+    let splits = split_decimal(amount, recipients, scale);
+
+    // Length invariant.
+    assert_eq!(splits.len(), recipients);
+
+    // Sum-conservation invariant.
+    let total: i128 = splits.iter().map(|d| d.mantissa()).sum();
+    assert_eq!(total, amount.mantissa());
+
+    // Monotonic-ordering invariant: the leading recipients absorb the leftover
+    // units, so shares are non-increasing for non-negative amounts.
+    if amount.mantissa() >= 0 {
+        for window in splits.windows(2) {
+            assert!(window[0] >= window[1]);
+        }
+    }
+});
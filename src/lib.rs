@@ -0,0 +1,6 @@
+//! divvysim: exact Decimal splitting in minimal units.
+//!
+//! The splitting logic lives in [`split`]; it is exposed as a library so tests,
+//! benchmarks, and the `fuzz/` harness can drive it directly.
+
+pub mod split;
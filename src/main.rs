@@ -1,8 +1,6 @@
 // src/main.rs
 
-// Import the split module (ensures that src/split.rs is compiled)
-mod split;
-
+use divvysim::split;
 use rust_decimal::Decimal;
 
 fn main() {
@@ -1,13 +1,122 @@
 use rust_decimal::Decimal;
-use rust_decimal::prelude::ToPrimitive; // needed for .mantissa()
-use rayon::prelude::*;
+use std::fmt;
+
+/// Errors that can occur while splitting a [`Decimal`].
+///
+/// The infallible [`split_decimal`], [`split_rescaled`], and
+/// [`split_decimal_weighted`] panic on these conditions; callers that handle
+/// refunds, chargebacks, or otherwise untrusted input should prefer the fallible
+/// [`try_split_decimal`], [`try_split_rescaled`], and
+/// [`try_split_decimal_weighted`], which surface them as `Err` instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitError {
+    /// The recipient count was zero, so the amount cannot be divided.
+    ZeroRecipients,
+    /// An intermediate or reconstructed value exceeded the 96-bit mantissa that
+    /// a [`Decimal`] can represent.
+    Overflow,
+    /// The weights supplied to a weighted split were empty or summed to zero, so
+    /// the amount cannot be apportioned.
+    InvalidWeights,
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitError::ZeroRecipients => write!(f, "cannot split among zero recipients"),
+            SplitError::Overflow => write!(f, "split value exceeded the 96-bit Decimal mantissa"),
+            SplitError::InvalidWeights => write!(f, "weights must be non-empty and sum to a positive total"),
+        }
+    }
+}
+
+impl std::error::Error for SplitError {}
+
+/// Rounding strategy applied when rescaling an amount down to a coarser scale
+/// drops precision. See [`split_rescaled`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingStrategy {
+    /// Round halves away from zero (e.g. `2.5 -> 3`, `-2.5 -> -3`).
+    RoundHalfUp,
+    /// Round halves to the nearest even digit — banker's rounding.
+    RoundHalfEven,
+    /// Discard the dropped digits, rounding toward zero.
+    Truncate,
+}
+
+/// The largest magnitude a [`Decimal`] mantissa can hold: its `lo`/`mid`/`hi`
+/// `u32` limbs all set, i.e. `2^96 - 1`.
+const MAX_MANTISSA: u128 = (1u128 << 96) - 1;
+
+/// Rescales a mantissa from scale `from` to scale `to`, applying `rounding` when
+/// the target scale is coarser and digits must be dropped.
+///
+/// Up-scaling is exact (a checked multiply by a power of ten); down-scaling
+/// divides by the power of ten and rounds the discarded remainder. All work is
+/// done on the magnitude with the sign reapplied, so the strategies behave
+/// symmetrically for negative amounts.
+fn rescale_mantissa(
+    m: i128,
+    from: u32,
+    to: u32,
+    rounding: RoundingStrategy,
+) -> Result<i128, SplitError> {
+    if to >= from {
+        let factor = 10i128
+            .checked_pow(to - from)
+            .ok_or(SplitError::Overflow)?;
+        return m.checked_mul(factor).ok_or(SplitError::Overflow);
+    }
+
+    let factor = 10u128
+        .checked_pow(from - to)
+        .ok_or(SplitError::Overflow)?;
+    let sign = m.signum();
+    let mag = m.unsigned_abs();
+    let quotient = mag / factor;
+    let remainder = mag % factor;
+    let rounded = match rounding {
+        RoundingStrategy::Truncate => quotient,
+        RoundingStrategy::RoundHalfUp => {
+            if remainder * 2 >= factor {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+        RoundingStrategy::RoundHalfEven => {
+            let doubled = remainder * 2;
+            if doubled > factor || (doubled == factor && quotient % 2 == 1) {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+    };
+    Ok(sign * rounded as i128)
+}
+
+/// Reconstructs a [`Decimal`] from a mantissa computed in `i128`, first checking
+/// that the value fits within the representable 96-bit range.
+///
+/// `Decimal::from_i128_with_scale` happily accepts (and then mangles or panics
+/// on) mantissas wider than 96 bits, so every reconstruction routes through
+/// here to fail loudly with [`SplitError::Overflow`] instead.
+fn checked_decimal(mantissa: i128, scale: u32) -> Result<Decimal, SplitError> {
+    if mantissa.unsigned_abs() > MAX_MANTISSA {
+        return Err(SplitError::Overflow);
+    }
+    Ok(Decimal::from_i128_with_scale(mantissa, scale))
+}
 
 /// Splits a given Decimal amount among a given number of recipients.
 ///
 /// The function assumes that the provided Decimal’s internal value (its mantissa)
 /// represents the amount in minimal units. For example, if you call:
 ///
-///     Decimal::from_i128_with_scale(10001, 2)
+/// ```ignore
+/// Decimal::from_i128_with_scale(10001, 2)
+/// ```
 ///
 /// that represents 100.01. The parameter `scale` is the desired scale for the split
 /// results. The function computes each recipient’s share in minimal units, then re-
@@ -16,36 +125,193 @@ use rayon::prelude::*;
 /// Note: All arithmetic (base share, remainder, and adjustments) is done on the
 /// underlying mantissa.
 pub fn split_decimal(amount: Decimal, recipients: usize, scale: u32) -> Vec<Decimal> {
-    // Get the raw underlying integer value (the mantissa).
+    split_decimal_iter(amount, recipients, scale).collect()
+}
+
+/// Lazily yields each recipient's share without materializing the whole vector.
+///
+/// The equal split is fully determined up front: with `base_share = raw /
+/// recipients` and `remainder = raw % recipients`, exactly the first
+/// `|remainder|` recipients receive one extra minimal unit (`+1` for positive
+/// amounts, `-1` for negative ones), and everyone else gets the base share. Each
+/// index is therefore computable in O(1) with no buffering and no post-hoc
+/// adjustment loop — useful when `recipients` runs into the millions and a
+/// `Vec` would be prohibitive. [`split_decimal`] is a thin `collect()` over this.
+///
+/// Panics on `recipients == 0`; use [`try_split_decimal`] for a checked split.
+pub fn split_decimal_iter(
+    amount: Decimal,
+    recipients: usize,
+    scale: u32,
+) -> impl Iterator<Item = Decimal> {
     let raw: i128 = amount.mantissa();
     let base_share = raw / (recipients as i128);
     let remainder = raw % (recipients as i128);
+    (0..recipients).map(move |i| {
+        let extra = if remainder >= 0 {
+            if (i as i128) < remainder { 1 } else { 0 }
+        } else if (i as i128) < -remainder {
+            -1
+        } else {
+            0
+        };
+        Decimal::from_i128_with_scale(base_share + extra, scale)
+    })
+}
+
+/// Converts a Decimal into its underlying integer representation (the mantissa).
+pub fn decimal_to_int(amount: Decimal, _scale: u32) -> i128 {
+    amount.mantissa()
+}
+
+/// Fallible sibling of [`split_decimal`] that returns a [`SplitError`] instead
+/// of panicking on invalid input.
+///
+/// The remainder distribution is sign-aware, so a negative `amount` (whose
+/// mantissa is negative) still splits into shares that sum to the input. The
+/// leftover `diff = raw - base_share * recipients` is driven to zero in
+/// whichever direction its sign
+/// points — one `+1` unit to the leading recipients when `raw` is positive, one
+/// `-1` unit when it is negative — so the sum invariant holds for refunds,
+/// chargebacks, and debit splits too.
+///
+/// Returns [`SplitError::ZeroRecipients`] when `recipients` is `0`.
+pub fn try_split_decimal(
+    amount: Decimal,
+    recipients: usize,
+    scale: u32,
+) -> Result<Vec<Decimal>, SplitError> {
+    if recipients == 0 {
+        return Err(SplitError::ZeroRecipients);
+    }
+
+    // Get the raw underlying integer value (the mantissa).
+    let raw: i128 = amount.mantissa();
+    let base_share = raw / (recipients as i128);
+
+    // Each recipient starts at the base share; the leftover minimal units (the
+    // remainder, which carries the sign of `raw`) are handed out one at a time
+    // to the leading recipients so the shares sum back to `raw` exactly. All
+    // arithmetic is checked so an outsized input fails rather than wrapping.
+    let mut shares: Vec<i128> = vec![base_share; recipients];
+    let allocated = base_share
+        .checked_mul(recipients as i128)
+        .ok_or(SplitError::Overflow)?;
+    let mut diff = raw.checked_sub(allocated).ok_or(SplitError::Overflow)?;
+    let step: i128 = if diff >= 0 { 1 } else { -1 };
+    let mut i = 0usize;
+    while diff != 0 {
+        shares[i] = shares[i].checked_add(step).ok_or(SplitError::Overflow)?;
+        diff -= step;
+        i += 1;
+    }
+
+    shares
+        .into_iter()
+        .map(|share| checked_decimal(share, scale))
+        .collect()
+}
+
+/// Splits an amount after first rescaling it to the target `scale`, instead of
+/// reinterpreting its raw mantissa.
+///
+/// [`split_decimal`] ignores the input's own scale and grabs its mantissa
+/// directly, so `Decimal::from_i128_with_scale(1234567, 0)` split at `scale = 2`
+/// silently becomes `12345.67` — a 100x value shift. This variant converts the
+/// amount from its actual scale to `scale` with proper decimal rescaling,
+/// applying `rounding` when down-scaling drops precision, and only then runs the
+/// per-unit remainder distribution. The shares therefore sum exactly to the
+/// *rescaled* amount with no surprising magnitude change.
+pub fn split_rescaled(
+    amount: Decimal,
+    recipients: usize,
+    scale: u32,
+    rounding: RoundingStrategy,
+) -> Vec<Decimal> {
+    try_split_rescaled(amount, recipients, scale, rounding).expect("split_rescaled: invalid input")
+}
+
+/// Fallible sibling of [`split_rescaled`]; see [`SplitError`] for the conditions
+/// it surfaces.
+pub fn try_split_rescaled(
+    amount: Decimal,
+    recipients: usize,
+    scale: u32,
+    rounding: RoundingStrategy,
+) -> Result<Vec<Decimal>, SplitError> {
+    let rescaled = rescale_mantissa(amount.mantissa(), amount.scale(), scale, rounding)?;
+    let amount = checked_decimal(rescaled, scale)?;
+    try_split_decimal(amount, recipients, scale)
+}
+
+/// Splits a given Decimal amount in proportion to a set of integer `weights`.
+///
+/// Where [`split_decimal`] divides the amount evenly, this apportions it by
+/// stake: a recipient with weight 3 receives three times the share of a
+/// recipient with weight 1. Like the equal split, the result is exact — the
+/// returned shares always sum back to the input amount with no minimal units
+/// lost or conjured.
+///
+/// It uses the classic largest-remainder (Hamilton) method on the mantissa:
+/// with `raw = amount.mantissa()` and `W = sum(weights)`, each recipient's base
+/// share is the floor of `raw * w_i / W`. The floors together leave
+/// `raw - sum(floors)` minimal units unallocated; those units are handed out one
+/// at a time to the recipients with the largest `raw * w_i % W` remainders, ties
+/// broken by lowest index so the result is deterministic.
+///
+/// Note: As with [`split_decimal`], all arithmetic is done on the underlying
+/// mantissa and the result is stamped with the requested `scale`.
+pub fn split_decimal_weighted(amount: Decimal, weights: &[u64], scale: u32) -> Vec<Decimal> {
+    try_split_decimal_weighted(amount, weights, scale).expect("split_decimal_weighted: invalid input")
+}
+
+/// Fallible sibling of [`split_decimal_weighted`].
+///
+/// The `raw * w_i` products the largest-remainder method needs can exceed the
+/// 96-bit mantissa for large amounts or heavy weights, so the arithmetic is
+/// checked and every reconstructed share is range-validated, returning
+/// [`SplitError::Overflow`] rather than wrapping or panicking.
+pub fn try_split_decimal_weighted(
+    amount: Decimal,
+    weights: &[u64],
+    scale: u32,
+) -> Result<Vec<Decimal>, SplitError> {
+    let raw: i128 = amount.mantissa();
+    let total_weight: i128 = weights.iter().map(|&w| w as i128).sum();
+    if weights.is_empty() || total_weight <= 0 {
+        return Err(SplitError::InvalidWeights);
+    }
+
+    // Base share (floored) and remainder for each recipient. Flooring (rather
+    // than i128's truncate-toward-zero) keeps every remainder in
+    // `[0, total_weight)` so the leftover is always non-negative, which makes
+    // the largest-remainder distribution below work for negative amounts too.
+    let mut bases: Vec<i128> = Vec::with_capacity(weights.len());
+    let mut remainders: Vec<i128> = Vec::with_capacity(weights.len());
+    for &w in weights {
+        let product = raw.checked_mul(w as i128).ok_or(SplitError::Overflow)?;
+        let base = product.div_euclid(total_weight);
+        bases.push(base);
+        remainders.push(product.rem_euclid(total_weight));
+    }
+
+    // Distribute the still-unallocated minimal units to the largest remainders,
+    // ties broken by lowest index for determinism.
+    let mut leftover = raw - bases.iter().sum::<i128>();
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]).then(a.cmp(&b)));
+    for &i in order.iter() {
+        if leftover <= 0 {
+            break;
+        }
+        bases[i] += 1;
+        leftover -= 1;
+    }
 
-    // Compute each recipient's share as (base_share + extra) minimal units.
-    let mut splits: Vec<Decimal> = (0..recipients)
-        .into_par_iter()
-        .map(|i| {
-            let extra = if (i as i128) < remainder { 1 } else { 0 };
-            Decimal::from_i128_with_scale(base_share + extra, scale)
-        })
-        .collect();
-
-    // Sum the shares (using the mantissa for accurate minimal-unit arithmetic).
-    let total_split: i128 = splits.iter().map(|d| d.mantissa()).sum();
-
-    // If there is any discrepancy between the computed total and the original raw amount,
-    // adjust the first recipient's share one minimal unit at a time.
-    let mut diff = raw - total_split;
-    let unit = 1; // one minimal unit in the new scale.
-    while diff > 0 {
-        let current = splits[0].mantissa();
-        let new_val = current + unit;
-        splits[0] = Decimal::from_i128_with_scale(new_val, scale);
-        let new_total: i128 = splits.iter().map(|d| d.mantissa()).sum();
-        diff = raw - new_total;
-    }
-
-    splits
+    bases
+        .into_iter()
+        .map(|share| checked_decimal(share, scale))
+        .collect()
 }
 
 #[cfg(test)]
@@ -220,11 +486,150 @@ mod tests {
         let total: i128 = splits.iter().map(|d| d.mantissa()).sum();
         assert_eq!(total, amount.mantissa());
     }
-}
 
-/// Converts a Decimal into its underlying integer representation (the mantissa).
-pub fn decimal_to_int(amount: Decimal, _scale: u32) -> i128 {
-    amount.mantissa()
+    #[test]
+    fn test_negative_amount_sum_invariant() {
+        // -100.01 split among 4: the sum must still equal the input.
+        let amount = Decimal::from_i128_with_scale(-10001, 2);
+        let recipients = 4;
+        let scale = 2;
+        let splits = try_split_decimal(amount, recipients, scale).unwrap();
+        let total: i128 = splits.iter().map(|d| d.mantissa()).sum();
+        assert_eq!(total, amount.mantissa());
+    }
+
+    #[test]
+    fn test_negative_amount_uneven() {
+        let amount = Decimal::from_i128_with_scale(-1235, 2);
+        let splits = try_split_decimal(amount, 7, 2).unwrap();
+        let total: i128 = splits.iter().map(|d| d.mantissa()).sum();
+        assert_eq!(total, amount.mantissa());
+    }
+
+    #[test]
+    fn test_try_split_zero_recipients() {
+        let amount = Decimal::from_i128_with_scale(100, 2);
+        assert_eq!(
+            try_split_decimal(amount, 0, 2),
+            Err(SplitError::ZeroRecipients)
+        );
+    }
+
+    #[test]
+    fn test_iter_matches_vec() {
+        let amount = Decimal::from_i128_with_scale(10001, 2);
+        let recipients = 7;
+        let scale = 2;
+        let from_iter: Vec<Decimal> = split_decimal_iter(amount, recipients, scale).collect();
+        assert_eq!(from_iter, split_decimal(amount, recipients, scale));
+    }
+
+    #[test]
+    fn test_iter_sum_invariant_lazy() {
+        // Drive a large recipient count through the iterator without collecting.
+        let amount = Decimal::from_i128_with_scale(4, 28);
+        let recipients = 100000;
+        let total: i128 = split_decimal_iter(amount, recipients, 28)
+            .map(|d| d.mantissa())
+            .sum();
+        assert_eq!(total, amount.mantissa());
+    }
+
+    #[test]
+    fn test_rescaled_preserves_magnitude() {
+        // 1,234,567 at scale 0 rescaled up to scale 2 is 1234567.00, whose
+        // mantissa is 123456700 — not the 12345.67 the naive split produced.
+        let amount = Decimal::from_i128_with_scale(1234567, 0);
+        let splits = split_rescaled(amount, 1, 2, RoundingStrategy::RoundHalfUp);
+        assert_eq!(splits[0], Decimal::from_i128_with_scale(123456700, 2));
+    }
+
+    #[test]
+    fn test_rescaled_sum_invariant_with_rounding() {
+        // Down-scaling from 4 to 2 decimal places drops precision; the shares
+        // must still sum to the rounded amount.
+        let amount = Decimal::from_i128_with_scale(1234567, 4); // 123.4567
+        let splits = try_split_rescaled(amount, 3, 2, RoundingStrategy::RoundHalfEven).unwrap();
+        let total: i128 = splits.iter().map(|d| d.mantissa()).sum();
+        let expected = rescale_mantissa(1234567, 4, 2, RoundingStrategy::RoundHalfEven).unwrap();
+        assert_eq!(total, expected);
+    }
+
+    #[test]
+    fn test_rounding_strategies_differ() {
+        // 125 at scale 1 (12.5) down to scale 0: half-up -> 13, banker's -> 12.
+        assert_eq!(rescale_mantissa(125, 1, 0, RoundingStrategy::RoundHalfUp), Ok(13));
+        assert_eq!(rescale_mantissa(125, 1, 0, RoundingStrategy::RoundHalfEven), Ok(12));
+        assert_eq!(rescale_mantissa(125, 1, 0, RoundingStrategy::Truncate), Ok(12));
+        assert_eq!(rescale_mantissa(-125, 1, 0, RoundingStrategy::RoundHalfUp), Ok(-13));
+    }
+
+    #[test]
+    fn test_overflow_rejected() {
+        // A mantissa at the 96-bit ceiling times a `u64::MAX` weight overflows
+        // the `raw * w_i` product, which must error rather than wrap or panic.
+        let amount = Decimal::MAX;
+        let result = try_split_decimal_weighted(amount, &[u64::MAX, 1], 0);
+        assert_eq!(result, Err(SplitError::Overflow));
+    }
+
+    #[test]
+    fn test_weighted_sum_invariant() {
+        let amount = Decimal::from_i128_with_scale(10001, 2); // 100.01
+        let weights = [1u64, 2, 3, 4];
+        let splits = split_decimal_weighted(amount, &weights, 2);
+        let total: i128 = splits.iter().map(|d| d.mantissa()).sum();
+        assert_eq!(total, amount.mantissa());
+    }
+
+    #[test]
+    fn test_weighted_proportional_shares() {
+        // 1000 units split 1:3 should give exactly 250 and 750.
+        let amount = Decimal::from_i128_with_scale(1000, 0);
+        let weights = [1u64, 3];
+        let splits = split_decimal_weighted(amount, &weights, 0);
+        assert_eq!(splits[0], Decimal::from_i128_with_scale(250, 0));
+        assert_eq!(splits[1], Decimal::from_i128_with_scale(750, 0));
+    }
+
+    #[test]
+    fn test_weighted_negative_sum_invariant() {
+        let amount = Decimal::from_i128_with_scale(-10001, 2);
+        let weights = [1u64, 1];
+        let splits = split_decimal_weighted(amount, &weights, 2);
+        let total: i128 = splits.iter().map(|d| d.mantissa()).sum();
+        assert_eq!(total, amount.mantissa());
+    }
+
+    #[test]
+    fn test_weighted_empty_weights_rejected() {
+        let amount = Decimal::from_i128_with_scale(100, 2);
+        assert_eq!(
+            try_split_decimal_weighted(amount, &[], 2),
+            Err(SplitError::InvalidWeights)
+        );
+    }
+
+    #[test]
+    fn test_weighted_zero_weights_rejected() {
+        let amount = Decimal::from_i128_with_scale(100, 2);
+        assert_eq!(
+            try_split_decimal_weighted(amount, &[0, 0], 2),
+            Err(SplitError::InvalidWeights)
+        );
+    }
+
+    #[test]
+    fn test_weighted_largest_remainder_ties_lowest_index() {
+        // 10 units, equal weights, 3 recipients: base 3 each, 1 leftover unit
+        // goes to the lowest index on a remainder tie.
+        let amount = Decimal::from_i128_with_scale(10, 0);
+        let weights = [1u64, 1, 1];
+        let splits = split_decimal_weighted(amount, &weights, 0);
+        assert_eq!(splits[0], Decimal::from_i128_with_scale(4, 0));
+        assert_eq!(splits[1], Decimal::from_i128_with_scale(3, 0));
+        assert_eq!(splits[2], Decimal::from_i128_with_scale(3, 0));
+    }
 }
 
 // --- End of src/split.rs ---